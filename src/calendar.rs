@@ -0,0 +1,132 @@
+use local::{self, LocalDate, DatePiece, Month, Weekday};
+use format::{Alignment, Arguments, DateFormat, Field, Locale, English, weekday_index};
+
+/// Which day starts a calendar grid's first column.
+#[derive(PartialEq, Eq, Clone, Show)]
+pub enum WeekStart {
+    Monday,
+    Sunday,
+}
+
+impl Copy for WeekStart { }
+
+/// The position of `weekday` in a grid that starts on `week_start`, as a
+/// column index counting from 0.
+fn column_of(weekday: Weekday, week_start: WeekStart) -> i64 {
+    let monday_based = weekday_index(weekday);
+    match week_start {
+        WeekStart::Monday => monday_based,
+        WeekStart::Sunday => (monday_based + 1) % 7,
+    }
+}
+
+/// The width of a single grid cell, including one spare column that
+/// separates its text from its neighbour's.
+fn cell_width(long_headers: bool) -> i64 {
+    (if long_headers { 9 } else { 3 }) + 1
+}
+
+fn weekday_header(week_start: WeekStart, long_headers: bool, locale: &Locale) -> Vec<String> {
+    use local::Weekday::*;
+    let columns = match week_start {
+        WeekStart::Monday => [Monday, Tuesday, Wednesday, Thursday, Friday, Saturday, Sunday],
+        WeekStart::Sunday => [Sunday, Monday, Tuesday, Wednesday, Thursday, Friday, Saturday],
+    };
+
+    let field = Field::WeekdayName(long_headers);
+    let args = Arguments { alignment: Some(Alignment::Left), width: Some(cell_width(long_headers) as usize), pad_char: None };
+    let header_format = DateFormat { fields: vec![ (field, args) ] };
+
+    // The week of 2017-01-02 runs Monday through Sunday, so it's a
+    // convenient way to get a real date for each weekday to format.
+    columns.iter().map(|&day| {
+        let day_of_month = 2 + weekday_index(day);
+        let representative = LocalDate::ymd(2017, Month::January, day_of_month as i8).unwrap();
+        header_format.clone().format_localized(representative, locale).unwrap()
+    }).collect()
+}
+
+/// Renders the whole month of `year`/`month` as an aligned text grid: a
+/// weekday header row, followed by one row per week, with day numbers
+/// right-aligned into their columns and blank cells for days outside
+/// the month.
+pub fn render_month(year: i64, month: Month, week_start: WeekStart, long_headers: bool, locale: &Locale) -> String {
+    let day_field = Field::Day;
+    let cell_width = cell_width(long_headers);
+    let day_args = Arguments { alignment: Some(Alignment::Right), width: Some(cell_width as usize), pad_char: None };
+    let day_format = DateFormat { fields: vec![ (day_field, day_args) ] };
+
+    let mut grid = String::new();
+    for header in weekday_header(week_start, long_headers, locale) {
+        grid.push_str(&header);
+    }
+    grid.push('\n');
+
+    let first = LocalDate::ymd(year, month, 1).unwrap();
+    let mut column = column_of(first.weekday(), week_start);
+    for _ in 0 .. (column * cell_width) {
+        grid.push(' ');
+    }
+
+    let mut when = first;
+    loop {
+        grid.push_str(&day_format.clone().format(when).unwrap());
+        column += 1;
+
+        if column == 7 {
+            grid.push('\n');
+            column = 0;
+        }
+
+        if when.day() == local::days_in_month(year, month) {
+            break;
+        }
+        when = when.succ();
+    }
+
+    if column != 0 {
+        for _ in 0 .. ((7 - column) * cell_width) {
+            grid.push(' ');
+        }
+        grid.push('\n');
+    }
+
+    grid
+}
+
+/// `render_month` with the crate's usual defaults: weeks start on Monday,
+/// headers use the short weekday names, and names are in English.
+pub fn month_calendar(year: i64, month: Month) -> String {
+    render_month(year, month, WeekStart::Monday, false, &English)
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use local::Weekday;
+
+    #[test]
+    fn monday_start_columns_are_unchanged() {
+        assert_eq!(column_of(Weekday::Monday, WeekStart::Monday), 0);
+        assert_eq!(column_of(Weekday::Sunday, WeekStart::Monday), 6);
+    }
+
+    #[test]
+    fn sunday_start_shifts_columns_by_one() {
+        assert_eq!(column_of(Weekday::Sunday, WeekStart::Sunday), 0);
+        assert_eq!(column_of(Weekday::Monday, WeekStart::Sunday), 1);
+    }
+
+    #[test]
+    fn january_2015_is_rendered_as_a_grid() {
+        // 2015-01-01 was a Thursday, so the first week is padded out to
+        // three blank columns, and the last week is padded with one.
+        assert_eq!(month_calendar(2015, Month::January), "\
+Mon Tue Wed Thu Fri Sat Sun \n\
+               1   2   3   4\n\
+   5   6   7   8   9  10  11\n\
+  12  13  14  15  16  17  18\n\
+  19  20  21  22  23  24  25\n\
+  26  27  28  29  30  31    \n")
+    }
+}
@@ -1,6 +1,34 @@
+use std::ascii::AsciiExt;
 use std::io;
 use local;
 use local::{LocalDate, DatePiece};
+use pad::{PadStr, Alignment as PadAlignment};
+
+/// A date that also carries a time-of-day and a UTC offset, the way
+/// `instant::Instant` does. Implemented by anything `DateFormat` can
+/// render the `:h`, `:H`, `:m`, `:s`, `:f`, `:z`, and `:P` fields from.
+pub trait TimePiece {
+    fn hour(&self) -> i8;
+    fn minute(&self) -> i8;
+    fn second(&self) -> i8;
+
+    /// The sub-second component, in nanoseconds.
+    fn nanosecond(&self) -> i32;
+
+    /// The UTC offset, in seconds, east of UTC.
+    fn utc_offset_seconds(&self) -> i32;
+
+    fn hour_12(&self) -> i8 {
+        match self.hour() % 12 {
+            0 => 12,
+            h => h,
+        }
+    }
+
+    fn is_pm(&self) -> bool {
+        self.hour() >= 12
+    }
+}
 
 #[derive(PartialEq, Eq, Clone, Show)]
 pub enum Field<'a> {
@@ -13,28 +41,95 @@ pub enum Field<'a> {
 
     Day,
     WeekdayName(bool),
+
+    Hour24,
+    Hour12,
+    Minute,
+    Second,
+    AmPm,
+    FractionalSecond(u8),
+    OffsetSeconds,
+
+    IsoWeek,
+    IsoWeekYear,
+    DayOfYear,
 }
 
 impl<'a> Copy for Field<'a> { }
 
 impl<'a> Field<'a> {
-    fn format(self, when: LocalDate, w: &mut io::MemWriter) -> io::IoResult<()> {
+    fn render(self, when: LocalDate, locale: &Locale) -> Result<String, FormatError> {
+        let text = match self {
+            Field::Literal(s)           => s.to_string(),
+            Field::Year                 => when.year().to_string(),
+            Field::YearOfCentury        => when.year_of_century().to_string(),
+            Field::MonthName(true)      => locale.long_month(when.month()).to_string(),
+            Field::MonthName(false)     => locale.short_month(when.month()).to_string(),
+            Field::Day                  => when.day().to_string(),
+            Field::WeekdayName(true)    => locale.long_weekday(when.weekday()).to_string(),
+            Field::WeekdayName(false)   => locale.short_weekday(when.weekday()).to_string(),
+            Field::IsoWeek              => when.iso_week_date().1.to_string(),
+            Field::IsoWeekYear          => when.iso_week_date().0.to_string(),
+            Field::DayOfYear            => when.day_of_year().to_string(),
+            Field::Hour24 | Field::Hour12 | Field::Minute | Field::Second |
+            Field::AmPm | Field::FractionalSecond(..) | Field::OffsetSeconds =>
+                return Err(FormatError::TimeFieldWithoutInstant),
+        };
+
+        Ok(text)
+    }
+
+    fn render_instant<T: DatePiece + TimePiece>(self, when: &T, locale: &Locale) -> String {
         match self {
-            Field::Literal(s)           => write!(w, "{}", s),
-            Field::Year                 => write!(w, "{}", when.year()),
-            Field::YearOfCentury        => write!(w, "{}", when.year_of_century()),
-            Field::MonthName(true)      => write!(w, "{}", long_month_name(when.month())),
-            Field::MonthName(false)     => write!(w, "{}", short_month_name(when.month())),
-            Field::Day                  => write!(w, "{}", when.day()),
-            Field::WeekdayName(true)    => write!(w, "{}", long_day_name(when.weekday())),
-            Field::WeekdayName(false)   => write!(w, "{}", short_day_name(when.weekday())),
+            Field::Hour24               => when.hour().to_string(),
+            Field::Hour12               => when.hour_12().to_string(),
+            Field::Minute               => when.minute().to_string(),
+            Field::Second               => when.second().to_string(),
+            Field::AmPm                 => if when.is_pm() { "pm".to_string() } else { "am".to_string() },
+            Field::FractionalSecond(digits) => format_fractional_second(when.nanosecond(), digits),
+            Field::OffsetSeconds        => format_offset_seconds(when.utc_offset_seconds()),
+            Field::Year                 => when.year().to_string(),
+            Field::YearOfCentury        => when.year_of_century().to_string(),
+            Field::MonthName(true)      => locale.long_month(when.month()).to_string(),
+            Field::MonthName(false)     => locale.short_month(when.month()).to_string(),
+            Field::Day                  => when.day().to_string(),
+            Field::WeekdayName(true)    => locale.long_weekday(when.weekday()).to_string(),
+            Field::WeekdayName(false)   => locale.short_weekday(when.weekday()).to_string(),
+            Field::IsoWeek              => when.iso_week_date().1.to_string(),
+            Field::IsoWeekYear          => when.iso_week_date().0.to_string(),
+            Field::DayOfYear            => when.day_of_year().to_string(),
+            Field::Literal(s)           => s.to_string(),
         }
     }
 }
 
+fn format_fractional_second(nanosecond: i32, digits: u8) -> String {
+    let divisor = 10i32.pow(9 - digits as u32);
+    let mut digits_str = (nanosecond / divisor).to_string();
+    while digits_str.len() < digits as usize {
+        digits_str = format!("0{}", digits_str);
+    }
+    digits_str
+}
+
+fn format_offset_seconds(offset_seconds: i32) -> String {
+    let sign = if offset_seconds < 0 { '-' } else { '+' };
+    let minutes_total = offset_seconds.abs() / 60;
+    format!("{}{:02}{:02}", sign, minutes_total / 60, minutes_total % 60)
+}
+
+/// Monday-based weekday index: Monday is 0, Sunday is 6.
+pub fn weekday_index(day: local::Weekday) -> i64 {
+    use local::Weekday::*;
+    match day {
+        Monday => 0, Tuesday => 1, Wednesday => 2, Thursday => 3,
+        Friday  => 4, Saturday => 5, Sunday    => 6,
+    }
+}
+
 #[derive(PartialEq, Eq, Clone, Show)]
 pub struct DateFormat<'a> {
-    pub fields: Vec<Field<'a>>,
+    pub fields: Vec<(Field<'a>, Arguments)>,
 }
 
 #[derive(PartialEq, Eq, Clone, Show)]
@@ -43,23 +138,41 @@ pub enum FormatError {
     OpenCurlyBrace(usize),
     CloseCurlyBrace(usize),
     MissingField(usize),
+
+    /// A time-of-day field (`:h`, `:H`, `:m`, `:s`, `:f`, `:z`, `:P`) was
+    /// rendered with `format`/`format_localized`, which only have a
+    /// `LocalDate` to work from. Use `format_instant` instead.
+    TimeFieldWithoutInstant,
 }
 
 impl Copy for FormatError { }
 
 #[derive(PartialEq, Eq, Clone, Show)]
-enum Alignment {
+pub enum Alignment {
     Left,
     Centre,
     Right,
 }
 
-struct Arguments {
-    alignment: Option<Alignment>,
-    width:     Option<usize>,
-    pad_char:  Option<char>,
+impl Alignment {
+    fn to_pad_alignment(self) -> PadAlignment {
+        match self {
+            Alignment::Left   => PadAlignment::Left,
+            Alignment::Centre => PadAlignment::Middle,
+            Alignment::Right  => PadAlignment::Right,
+        }
+    }
+}
+
+#[derive(PartialEq, Eq, Clone, Show)]
+pub struct Arguments {
+    pub alignment: Option<Alignment>,
+    pub width:     Option<usize>,
+    pub pad_char:  Option<char>,
 }
 
+impl Copy for Arguments { }
+
 impl Arguments {
     pub fn empty() -> Arguments {
         Arguments {
@@ -72,13 +185,51 @@ impl Arguments {
     pub fn is_empty(&self) -> bool {
         self.alignment.is_none() && self.width.is_none() && self.pad_char.is_none()
     }
+
+    fn pad(self, text: String) -> String {
+        if self.is_empty() {
+            return text;
+        }
+
+        let alignment = self.alignment.unwrap_or(Alignment::Left).to_pad_alignment();
+        match self.width {
+            Some(width) => text.pad(width, self.pad_char.unwrap_or(' '), alignment, false),
+            None        => text,
+        }
+    }
 }
 
 impl<'a> DateFormat<'a> {
-    pub fn format(self, when: LocalDate) -> String {
+    /// Fails with `FormatError::TimeFieldWithoutInstant` if `self` contains
+    /// a time-of-day field; use `format_instant` for those.
+    pub fn format(self, when: LocalDate) -> Result<String, FormatError> {
+        self.format_localized(when, &English)
+    }
+
+    /// Like `format`, but renders month and weekday names through `locale`
+    /// instead of always using English.
+    pub fn format_localized(self, when: LocalDate, locale: &Locale) -> Result<String, FormatError> {
         let mut buf = io::MemWriter::new();
-        for bit in self.fields.into_iter() {
-            bit.format(when, &mut buf);
+        for (field, args) in self.fields.into_iter() {
+            let text = args.pad(try! { field.render(when, locale) });
+            write!(buf, "{}", text).unwrap();
+        }
+        Ok(String::from_utf8(buf.into_inner()).unwrap())
+    }
+
+    /// Like `format`, but also renders the time-of-day and offset fields
+    /// (`:h`, `:H`, `:m`, `:s`, `:f`, `:z`, `:P`) by reading them off any
+    /// `DatePiece + TimePiece` value rather than a plain `LocalDate`.
+    pub fn format_instant<T: DatePiece + TimePiece>(self, when: T) -> String {
+        self.format_instant_localized(when, &English)
+    }
+
+    /// The `format_instant` equivalent of `format_localized`.
+    pub fn format_instant_localized<T: DatePiece + TimePiece>(self, when: T, locale: &Locale) -> String {
+        let mut buf = io::MemWriter::new();
+        for (field, args) in self.fields.into_iter() {
+            let text = args.pad(field.render_instant(&when, locale));
+            write!(buf, "{}", text).unwrap();
         }
         String::from_utf8(buf.into_inner()).unwrap()
     }
@@ -94,11 +245,223 @@ impl<'a> DateFormat<'a> {
 
         Ok(DateFormat { fields: parser.fields })
     }
+
+    /// Runs this format string in reverse, reading `input` field-by-field
+    /// and building up a `LocalDate` from the pieces. Literals in the
+    /// format string must match `input` verbatim; names are matched
+    /// longest-first and case-insensitively.
+    pub fn parse_date(&self, input: &str) -> Result<LocalDate, ParseError> {
+        let mut parsed = Parsed::empty();
+        let mut rest = input;
+        let mut consumed = 0usize;
+
+        for &(field, args) in self.fields.iter() {
+            match field {
+                Field::Literal(s) => {
+                    if rest.starts_with(s) {
+                        rest = rest.slice_from(s.len());
+                        consumed += s.len();
+                    }
+                    else {
+                        return Err(ParseError::LiteralMismatch(consumed));
+                    }
+                },
+
+                Field::Year => {
+                    let (value, len) = try! { take_digits(rest, consumed, args.width) };
+                    parsed.year = Some(value);
+                    rest = rest.slice_from(len);
+                    consumed += len;
+                },
+
+                Field::YearOfCentury => {
+                    let (value, len) = try! { take_digits(rest, consumed, args.width) };
+                    parsed.year = Some(resolve_year_of_century(value));
+                    rest = rest.slice_from(len);
+                    consumed += len;
+                },
+
+                Field::Day => {
+                    let (value, len) = try! { take_digits(rest, consumed, args.width) };
+                    if value < 1 || value > 31 {
+                        return Err(ParseError::OutOfRange);
+                    }
+                    parsed.day = Some(value as i8);
+                    rest = rest.slice_from(len);
+                    consumed += len;
+                },
+
+                Field::MonthName(_) => {
+                    let (month, len) = try! { lookup_month_name(rest, consumed) };
+                    parsed.month = Some(month);
+                    rest = rest.slice_from(len);
+                    consumed += len;
+                },
+
+                Field::WeekdayName(_) => {
+                    let (weekday, len) = try! { lookup_weekday_name(rest, consumed) };
+                    parsed.weekday = Some(weekday);
+                    rest = rest.slice_from(len);
+                    consumed += len;
+                },
+
+                _ => return Err(ParseError::UnsupportedField(consumed)),
+            }
+        }
+
+        if !rest.is_empty() {
+            return Err(ParseError::TrailingInput(consumed));
+        }
+
+        parsed.resolve()
+    }
+}
+
+#[derive(PartialEq, Eq, Clone, Show)]
+pub enum ParseError {
+    /// A literal piece of the format string didn't match the input at the given position.
+    LiteralMismatch(usize),
+
+    /// A numeric field expected at least one digit, but didn't find one.
+    ExpectedDigit(usize),
+
+    /// A month or weekday name didn't match any of the known names.
+    UnknownName(usize),
+
+    /// A field was parsed that `parse_date` doesn't know how to resolve into a date.
+    UnsupportedField(usize),
+
+    /// There was leftover input once every field in the format had been consumed.
+    TrailingInput(usize),
+
+    /// A parsed number is too large or out of range for its field, or the
+    /// parsed year, month, and day don't form a valid date.
+    OutOfRange,
+
+    /// The format string didn't contain enough fields to build a complete date.
+    MissingField,
+
+    /// A weekday name was parsed, but it doesn't match the weekday the
+    /// rest of the fields resolve to.
+    WeekdayMismatch,
+}
+
+impl Copy for ParseError { }
+
+/// The date components accumulated while walking a `DateFormat`'s fields
+/// against an input string, ready to be resolved into a `LocalDate`.
+struct Parsed {
+    year:    Option<i64>,
+    month:   Option<local::Month>,
+    day:     Option<i8>,
+    weekday: Option<local::Weekday>,
+}
+
+impl Parsed {
+    fn empty() -> Parsed {
+        Parsed { year: None, month: None, day: None, weekday: None }
+    }
+
+    fn resolve(self) -> Result<LocalDate, ParseError> {
+        let year  = try! { self.year.ok_or(ParseError::MissingField) };
+        let month = try! { self.month.ok_or(ParseError::MissingField) };
+        let day   = try! { self.day.ok_or(ParseError::MissingField) };
+
+        let date = match LocalDate::ymd(year, month, day) {
+            Ok(date)  => date,
+            Err(..)   => return Err(ParseError::OutOfRange),
+        };
+
+        if let Some(weekday) = self.weekday {
+            if weekday != date.weekday() {
+                return Err(ParseError::WeekdayMismatch);
+            }
+        }
+
+        Ok(date)
+    }
+}
+
+// Bounded by `max_len` (the field's format width, if it has one) so that
+// fixed-width numeric fields don't greedily swallow digits belonging to
+// whatever follows them, e.g. `Year` in `"{:Y:4}{:D:2}"` reading only 4
+// digits of `"20240103"` rather than all 8.
+fn take_digits(input: &str, position: usize, max_len: Option<usize>) -> Result<(i64, usize), ParseError> {
+    let available = input.chars().take_while(|c| c.is_digit(10)).count();
+    let len = match max_len {
+        Some(width) if width < available => width,
+        _                                 => available,
+    };
+
+    if len == 0 {
+        return Err(ParseError::ExpectedDigit(position));
+    }
+
+    let digits = input.slice_to(len);
+    let value = try! { digits.parse().map_err(|_| ParseError::OutOfRange) };
+    Ok((value, len))
+}
+
+fn resolve_year_of_century(value: i64) -> i64 {
+    if value < 69 { 2000 + value } else { 1900 + value }
+}
+
+fn lookup_month_name(input: &str, position: usize) -> Result<(local::Month, usize), ParseError> {
+    use local::Month::*;
+
+    let candidates = [
+        (January,   "January",   "Jan"), (February,  "February",  "Feb"),
+        (March,     "March",     "Mar"), (April,     "April",     "Apr"),
+        (May,       "May",       "May"), (June,      "June",      "Jun"),
+        (July,      "July",      "Jul"), (August,    "August",    "Aug"),
+        (September, "September", "Sep"), (October,   "October",   "Oct"),
+        (November,  "November",  "Nov"), (December,  "December",  "Dec"),
+    ];
+
+    lookup_name(input, position, &candidates)
+}
+
+fn lookup_weekday_name(input: &str, position: usize) -> Result<(local::Weekday, usize), ParseError> {
+    use local::Weekday::*;
+
+    let candidates = [
+        (Monday,    "Monday",    "Mon"), (Tuesday,  "Tuesday",  "Tue"),
+        (Wednesday, "Wednesday", "Wed"), (Thursday, "Thursday", "Thu"),
+        (Friday,    "Friday",    "Fri"), (Saturday, "Saturday", "Sat"),
+        (Sunday,    "Sunday",    "Sun"),
+    ];
+
+    lookup_name(input, position, &candidates)
+}
+
+/// Matches `input` against a table of `(value, long name, short name)`
+/// triples, case-insensitively and longest-match first, so `"March"` is
+/// consumed whole rather than as `"Mar"` with `"ch"` left over.
+fn lookup_name<T: Copy>(input: &str, position: usize, candidates: &[(T, &str, &str)]) -> Result<(T, usize), ParseError> {
+    let lower_input = input.to_ascii_lowercase();
+    let mut best: Option<(T, usize)> = None;
+
+    for &(value, long, short) in candidates.iter() {
+        for name in [long, short].iter() {
+            if lower_input.starts_with(&*name.to_ascii_lowercase()) {
+                let is_longer = match best {
+                    Some((_, len)) => name.len() > len,
+                    None           => true,
+                };
+
+                if is_longer {
+                    best = Some((value, name.len()));
+                }
+            }
+        }
+    }
+
+    best.ok_or(ParseError::UnknownName(position))
 }
 
 struct FormatParser<'a, I> {
     iter: I,
-    fields: Vec<Field<'a>>,
+    fields: Vec<(Field<'a>, Arguments)>,
     input: &'a str,
 }
 
@@ -125,7 +488,7 @@ impl<'a, I: Iterator<Item=(usize, char)>> FormatParser<'a, I> {
                     if let Some(pos) = anchor {
                         anchor = None;
                         let field = Field::Literal(self.input.slice(pos, new_pos));
-                        self.fields.push(field);
+                        self.fields.push((field, Arguments::empty()));
                     }
 
                     let field = try! { self.parse_a_thing(new_pos) };
@@ -143,40 +506,170 @@ impl<'a, I: Iterator<Item=(usize, char)>> FormatParser<'a, I> {
 
         if let Some(pos) = anchor {
             let field = Field::Literal(self.input.slice_from(pos));
-            self.fields.push(field);
+            self.fields.push((field, Arguments::empty()));
         }
 
         Ok(())
     }
 
-    fn parse_a_thing(&mut self, open_brace_position: usize) -> Result<Field<'a>, FormatError> {
-        let mut args = Arguments::empty();
-        let mut bit = None;
+    // Parses the field letter directly after the opening brace, then looks
+    // for an optional second `:`-separated section carrying the alignment,
+    // width, and pad character, e.g. `{:D:>04}`.
+    fn parse_a_thing(&mut self, open_brace_position: usize) -> Result<(Field<'a>, Arguments), FormatError> {
+        let bit = match self.next() {
+            Some((_, ':')) => match self.next() {
+                Some((_, 'Y')) => Field::Year,
+                Some((_, 'y')) => Field::YearOfCentury,
+                Some((_, 'M')) => Field::MonthName(true),
+                Some((_, 'D')) => Field::Day,
+                Some((_, 'E')) => Field::WeekdayName(true),
+                Some((_, 'h')) => Field::Hour24,
+                Some((_, 'H')) => Field::Hour12,
+                Some((_, 'm')) => Field::Minute,
+                Some((_, 's')) => Field::Second,
+                Some((_, 'f')) => Field::FractionalSecond(3),
+                Some((_, 'z')) => Field::OffsetSeconds,
+                Some((_, 'P')) => Field::AmPm,
+                Some((_, 'w')) => Field::IsoWeek,
+                Some((_, 'W')) => Field::IsoWeekYear,
+                Some((_, 'o')) => Field::DayOfYear,
+                Some((pos, c)) => return Err(FormatError::InvalidChar(c, true, pos)),
+                None => return Err(FormatError::OpenCurlyBrace(open_brace_position)),
+            },
+            Some((_, '}')) => return Err(FormatError::MissingField(open_brace_position)),
+            Some((pos, c)) => return Err(FormatError::InvalidChar(c, false, pos)),
+            None => return Err(FormatError::OpenCurlyBrace(open_brace_position)),
+        };
+
+        let args = match self.next() {
+            Some((_, '}')) => Arguments::empty(),
+            Some((_, ':')) => try! { self.parse_arguments(open_brace_position) },
+            Some((pos, c)) => return Err(FormatError::InvalidChar(c, false, pos)),
+            None => return Err(FormatError::OpenCurlyBrace(open_brace_position)),
+        };
+
+        Ok((bit, args))
+    }
+
+    // Parses `[alignment][width][pad_char]` followed by the closing brace.
+    // Alignment is one of `>` (Right), `<` (Left), or `^` (Centre); width is
+    // a run of digits; a leading zero in the width implies a `0` pad char
+    // unless a trailing character overrides it explicitly.
+    fn parse_arguments(&mut self, open_brace_position: usize) -> Result<Arguments, FormatError> {
+        let mut alignment = None;
+        let mut next = self.next();
+
+        match next {
+            Some((_, '>')) => { alignment = Some(Alignment::Right);  next = self.next(); },
+            Some((_, '<')) => { alignment = Some(Alignment::Left);   next = self.next(); },
+            Some((_, '^')) => { alignment = Some(Alignment::Centre); next = self.next(); },
+            _ => {},
+        }
 
+        let mut digits = String::new();
         loop {
-            match self.next() {
-                Some((pos, ':')) => {
-                    let bitlet = match self.next() {
-                        Some((_, 'Y')) => Field::Year,
-                        Some((_, 'y')) => Field::YearOfCentury,
-                        Some((_, 'M')) => Field::MonthName(true),
-                        Some((_, 'D')) => Field::Day,
-                        Some((_, 'E')) => Field::WeekdayName(true),
-                        Some((pos, c)) => return Err(FormatError::InvalidChar(c, true, pos)),
-                        None => return Err(FormatError::OpenCurlyBrace(open_brace_position)),
-                    };
-
-                    bit = Some(bitlet);
+            match next {
+                Some((_, c)) if c.is_digit(10) => {
+                    digits.push(c);
+                    next = self.next();
                 },
-                Some((_, '}')) => break,
-                Some((pos, c)) => return Err(FormatError::InvalidChar(c, false, pos)),
-                None => return Err(FormatError::OpenCurlyBrace(open_brace_position)),
-            };
+                _ => break,
+            }
+        }
+
+        let mut pad_char = if digits.len() > 1 && digits.starts_with("0") { Some('0') } else { None };
+
+        match next {
+            Some((_, '}')) => {},
+            Some((_, c)) => {
+                pad_char = Some(c);
+                match self.next() {
+                    Some((_, '}')) => {},
+                    Some((pos, c)) => return Err(FormatError::InvalidChar(c, false, pos)),
+                    None => return Err(FormatError::OpenCurlyBrace(open_brace_position)),
+                }
+            },
+            None => return Err(FormatError::OpenCurlyBrace(open_brace_position)),
         }
 
-        match bit {
-            Some(b) => Ok(b),
-            None    => Err(FormatError::MissingField(open_brace_position)),
+        let width = if digits.is_empty() { None } else { digits.parse().ok() };
+
+        // A zero pad char with no explicit alignment arrow means the
+        // writer wants `printf`-style zero-padding (e.g. `{:D:04}`), which
+        // only makes sense right-aligned; `Alignment::Left`, the default,
+        // would pad the zeros onto the wrong side.
+        if alignment.is_none() && pad_char == Some('0') {
+            alignment = Some(Alignment::Right);
+        }
+
+        Ok(Arguments { alignment: alignment, width: width, pad_char: pad_char })
+    }
+}
+
+/// A set of month and weekday names that `DateFormat` can render `{:M}`
+/// and `{:E}` through, so output isn't locked to English.
+pub trait Locale {
+    fn long_month(&self, month: local::Month) -> &str;
+    fn short_month(&self, month: local::Month) -> &str;
+    fn long_weekday(&self, day: local::Weekday) -> &str;
+    fn short_weekday(&self, day: local::Weekday) -> &str;
+}
+
+/// The locale used by `format` and `format_instant` when no other locale is given.
+pub struct English;
+
+impl Locale for English {
+    fn long_month(&self, month: local::Month) -> &str { long_month_name(month) }
+    fn short_month(&self, month: local::Month) -> &str { short_month_name(month) }
+    fn long_weekday(&self, day: local::Weekday) -> &str { long_day_name(day) }
+    fn short_weekday(&self, day: local::Weekday) -> &str { short_day_name(day) }
+}
+
+/// A French locale, for `{:M}`/`{:E}` output such as `"mardi 3 juin"`.
+pub struct French;
+
+impl Locale for French {
+    fn long_month(&self, month: local::Month) -> &str {
+        use local::Month::*;
+        match month {
+            January   => "janvier",    February  => "février",
+            March     => "mars",       April     => "avril",
+            May       => "mai",        June      => "juin",
+            July      => "juillet",    August    => "août",
+            September => "septembre",  October   => "octobre",
+            November  => "novembre",   December  => "décembre",
+        }
+    }
+
+    fn short_month(&self, month: local::Month) -> &str {
+        use local::Month::*;
+        match month {
+            January   => "janv",  February  => "févr",
+            March     => "mars",  April     => "avr",
+            May       => "mai",   June      => "juin",
+            July      => "juil",  August    => "août",
+            September => "sept",  October   => "oct",
+            November  => "nov",   December  => "déc",
+        }
+    }
+
+    fn long_weekday(&self, day: local::Weekday) -> &str {
+        use local::Weekday::*;
+        match day {
+            Monday    => "lundi",     Tuesday   => "mardi",
+            Wednesday => "mercredi",  Thursday  => "jeudi",
+            Friday    => "vendredi",  Saturday  => "samedi",
+            Sunday    => "dimanche",
+        }
+    }
+
+    fn short_weekday(&self, day: local::Weekday) -> &str {
+        use local::Weekday::*;
+        match day {
+            Monday    => "lun",  Tuesday   => "mar",
+            Wednesday => "mer",  Thursday  => "jeu",
+            Friday    => "ven",  Saturday  => "sam",
+            Sunday    => "dim",
         }
     }
 }
@@ -231,7 +724,11 @@ fn short_day_name(day: local::Weekday) -> &'static str {
 mod test {
     pub use super::DateFormat;
     pub use super::Field::*;
-    pub use super::FormatError;
+    pub use super::{Arguments, FormatError};
+
+    fn none<'a>(field: super::Field<'a>) -> (super::Field<'a>, Arguments) {
+        (field, Arguments::empty())
+    }
 
     mod parse {
         use super::*;
@@ -243,27 +740,35 @@ mod test {
 
         #[test]
         fn entirely_literal() {
-            assert_eq!(DateFormat::parse("Date!").unwrap(), DateFormat { fields: vec![ Literal("Date!") ] })
+            assert_eq!(DateFormat::parse("Date!").unwrap(), DateFormat { fields: vec![ none(Literal("Date!")) ] })
         }
 
         #[test]
         fn single_element() {
-            assert_eq!(DateFormat::parse("{:Y}").unwrap(), DateFormat { fields: vec![ Year ] })
+            assert_eq!(DateFormat::parse("{:Y}").unwrap(), DateFormat { fields: vec![ none(Year) ] })
         }
 
         #[test]
         fn two_long_years() {
-            assert_eq!(DateFormat::parse("{:Y}{:Y}").unwrap(), DateFormat { fields: vec![ Year, Year ] })
+            assert_eq!(DateFormat::parse("{:Y}{:Y}").unwrap(), DateFormat { fields: vec![ none(Year), none(Year) ] })
         }
 
         #[test]
         fn surrounded() {
-            assert_eq!(DateFormat::parse("({:D})").unwrap(), DateFormat { fields: vec![ Literal("("), Day, Literal(")") ] })
+            assert_eq!(DateFormat::parse("({:D})").unwrap(), DateFormat { fields: vec![ none(Literal("(")), none(Day), none(Literal(")")) ] })
         }
 
         #[test]
         fn a_bunch_of_elements() {
-            assert_eq!(DateFormat::parse("{:Y}-{:M}-{:D}").unwrap(), DateFormat { fields: vec![ Year, Literal("-"), MonthName(true), Literal("-"), Day ] })
+            assert_eq!(DateFormat::parse("{:Y}-{:M}-{:D}").unwrap(), DateFormat { fields: vec![ none(Year), none(Literal("-")), none(MonthName(true)), none(Literal("-")), none(Day) ] })
+        }
+
+        #[test]
+        fn a_timestamp() {
+            assert_eq!(DateFormat::parse("{:h}:{:m}:{:s}.{:f}{:z}").unwrap(), DateFormat { fields: vec![
+                none(Hour24), none(Literal(":")), none(Minute), none(Literal(":")), none(Second),
+                none(Literal(".")), none(FractionalSecond(3)), none(OffsetSeconds),
+            ] })
         }
 
         #[test]
@@ -308,4 +813,248 @@ mod test {
 //             assert_eq!(DateFormat::parse("}}").unwrap(), DateFormat { fields: vec![ Literal("}") ] })
 //         }
     }
+
+    mod arguments {
+        use super::*;
+
+        #[test]
+        fn right_aligned_zero_padded() {
+            let fields = DateFormat::parse("{:D:>04}").unwrap().fields;
+            assert_eq!(fields, vec![ (Day, Arguments { alignment: Some(super::super::Alignment::Right), width: Some(4), pad_char: Some('0') }) ])
+        }
+
+        #[test]
+        fn left_aligned() {
+            let fields = DateFormat::parse("{:M:<10}").unwrap().fields;
+            assert_eq!(fields, vec![ (MonthName(true), Arguments { alignment: Some(super::super::Alignment::Left), width: Some(10), pad_char: None }) ])
+        }
+
+        #[test]
+        fn centred_with_explicit_pad_char() {
+            let fields = DateFormat::parse("{:E:^12*}").unwrap().fields;
+            assert_eq!(fields, vec![ (WeekdayName(true), Arguments { alignment: Some(super::super::Alignment::Centre), width: Some(12), pad_char: Some('*') }) ])
+        }
+
+        #[test]
+        fn width_with_no_alignment() {
+            let fields = DateFormat::parse("{:Y:8}").unwrap().fields;
+            assert_eq!(fields, vec![ (Year, Arguments { alignment: None, width: Some(8), pad_char: None }) ])
+        }
+
+        #[test]
+        fn zero_padded_day_is_rendered_padded() {
+            let date = local::LocalDate::ymd(2015, local::Month::January, 9).unwrap();
+            assert_eq!(DateFormat::parse("{:D:>02}").unwrap().format(date).unwrap(), "09")
+        }
+
+        #[test]
+        fn centred_month_name_is_rendered_padded() {
+            let date = local::LocalDate::ymd(2015, local::Month::May, 1).unwrap();
+            assert_eq!(DateFormat::parse("{:M:^7}").unwrap().format(date).unwrap(), "  May  ")
+        }
+
+        #[test]
+        fn implicit_zero_pad_without_arrow_defaults_to_right_aligned() {
+            let fields = DateFormat::parse("{:D:04}").unwrap().fields;
+            assert_eq!(fields, vec![ (Day, Arguments { alignment: Some(super::super::Alignment::Right), width: Some(4), pad_char: Some('0') }) ])
+        }
+
+        #[test]
+        fn implicit_zero_pad_without_arrow_is_rendered_right_aligned() {
+            let date = local::LocalDate::ymd(2015, local::Month::January, 4).unwrap();
+            assert_eq!(DateFormat::parse("{:D:04}").unwrap().format(date).unwrap(), "0004")
+        }
+    }
+
+    mod format_instant {
+        use super::*;
+        use super::super::TimePiece;
+        use local::{DatePiece, Month, Weekday};
+
+        #[derive(Clone, Copy)]
+        struct Moment;
+
+        impl DatePiece for Moment {
+            fn year(&self) -> i64 { 2015 }
+            fn year_of_century(&self) -> i64 { 15 }
+            fn month(&self) -> Month { Month::January }
+            fn day(&self) -> i8 { 9 }
+            fn weekday(&self) -> Weekday { Weekday::Friday }
+        }
+
+        impl TimePiece for Moment {
+            fn hour(&self) -> i8 { 13 }
+            fn minute(&self) -> i8 { 5 }
+            fn second(&self) -> i8 { 9 }
+            fn nanosecond(&self) -> i32 { 0 }
+            fn utc_offset_seconds(&self) -> i32 { 0 }
+        }
+
+        #[test]
+        fn time_fields_are_rendered() {
+            assert_eq!(DateFormat::parse("{:H}:{:m}:{:s} {:P}").unwrap().format_instant(Moment), "1:5:9 pm")
+        }
+
+        #[test]
+        fn time_field_with_plain_format_is_an_error() {
+            let date = local::LocalDate::ymd(2015, local::Month::January, 9).unwrap();
+            assert_eq!(DateFormat::parse("{:H}").unwrap().format(date), Err(FormatError::TimeFieldWithoutInstant))
+        }
+
+        #[derive(Clone, Copy)]
+        struct OffsetMoment;
+
+        impl DatePiece for OffsetMoment {
+            fn year(&self) -> i64 { 2015 }
+            fn year_of_century(&self) -> i64 { 15 }
+            fn month(&self) -> Month { Month::January }
+            fn day(&self) -> i8 { 9 }
+            fn weekday(&self) -> Weekday { Weekday::Friday }
+        }
+
+        impl TimePiece for OffsetMoment {
+            fn hour(&self) -> i8 { 13 }
+            fn minute(&self) -> i8 { 5 }
+            fn second(&self) -> i8 { 9 }
+            fn nanosecond(&self) -> i32 { 123456789 }
+            fn utc_offset_seconds(&self) -> i32 { -16200 }
+        }
+
+        #[test]
+        fn fractional_second_is_truncated_to_the_requested_width() {
+            assert_eq!(DateFormat::parse("{:f}").unwrap().format_instant(OffsetMoment), "123")
+        }
+
+        #[test]
+        fn negative_offset_seconds_are_rendered() {
+            // -16200 seconds is 4 hours 30 minutes west of UTC.
+            assert_eq!(DateFormat::parse("{:z}").unwrap().format_instant(OffsetMoment), "-0430")
+        }
+    }
+
+    mod parse_date {
+        pub use super::*;
+        pub use super::super::ParseError;
+
+        #[test]
+        fn literal_mismatch() {
+            let fmt = DateFormat::parse("on {:D}").unwrap();
+            assert_eq!(fmt.parse_date("won 9"), Err(ParseError::LiteralMismatch(0)))
+        }
+
+        #[test]
+        fn expected_digit() {
+            let fmt = DateFormat::parse("{:D}").unwrap();
+            assert_eq!(fmt.parse_date("ninth"), Err(ParseError::ExpectedDigit(0)))
+        }
+
+        #[test]
+        fn unknown_month_name() {
+            let fmt = DateFormat::parse("{:M}").unwrap();
+            assert_eq!(fmt.parse_date("Blorch"), Err(ParseError::UnknownName(0)))
+        }
+
+        #[test]
+        fn trailing_input() {
+            let fmt = DateFormat::parse("{:D}").unwrap();
+            assert_eq!(fmt.parse_date("9th"), Err(ParseError::TrailingInput(1)))
+        }
+
+        #[test]
+        fn missing_field() {
+            let fmt = DateFormat::parse("{:D}").unwrap();
+            assert_eq!(fmt.parse_date("9"), Err(ParseError::MissingField))
+        }
+
+        #[test]
+        fn fixed_width_fields_round_trip() {
+            // Without the field widths bounding each digit run, `Year`
+            // would greedily swallow all six digits and leave nothing
+            // for `Day` to parse.
+            let fmt = DateFormat::parse("{:Y:4}{:D:>02}").unwrap();
+            let date = local::LocalDate::ymd(2024, local::Month::January, 3).unwrap();
+            assert_eq!(fmt.clone().format(date).unwrap(), "202403");
+            assert_eq!(fmt.parse_date("202403"), Ok(date));
+        }
+
+        #[test]
+        fn unbounded_day_value_out_of_range_is_rejected() {
+            // No explicit width on `{:D}`, so `take_digits` would otherwise
+            // swallow all three digits and wrap `257 as i8` into `1`.
+            let fmt = DateFormat::parse("{:D}").unwrap();
+            assert_eq!(fmt.parse_date("257"), Err(ParseError::OutOfRange))
+        }
+
+        #[test]
+        fn overlong_digit_run_is_rejected_not_panicked() {
+            let fmt = DateFormat::parse("{:D}").unwrap();
+            assert_eq!(fmt.parse_date("123456789012345678901"), Err(ParseError::OutOfRange))
+        }
+
+        #[test]
+        fn mismatched_weekday_name_is_rejected() {
+            let fmt = DateFormat::parse("{:E}, {:M} {:D}, {:Y}").unwrap();
+            // 2015-01-09 was a Friday, not a Monday.
+            assert_eq!(fmt.parse_date("Monday, January 9, 2015"), Err(ParseError::WeekdayMismatch))
+        }
+
+        #[test]
+        fn matching_weekday_name_is_accepted() {
+            let fmt = DateFormat::parse("{:E}, {:M} {:D}, {:Y}").unwrap();
+            let date = local::LocalDate::ymd(2015, local::Month::January, 9).unwrap();
+            assert_eq!(fmt.parse_date("Friday, January 9, 2015"), Ok(date))
+        }
+    }
+
+    mod locale {
+        use super::DateFormat;
+        use super::super::{English, French, Locale};
+        use local::Month;
+        use local::Weekday;
+
+        #[test]
+        fn english_long_month() {
+            assert_eq!(English.long_month(Month::June), "June");
+        }
+
+        #[test]
+        fn french_long_month() {
+            assert_eq!(French.long_month(Month::June), "juin");
+        }
+
+        #[test]
+        fn french_short_weekday() {
+            assert_eq!(French.short_weekday(Weekday::Tuesday), "mar");
+        }
+
+        #[test]
+        fn french_month_name_is_rendered() {
+            let date = local::LocalDate::ymd(2015, Month::June, 18).unwrap();
+            assert_eq!(DateFormat::parse("{:M}").unwrap().format_localized(date, &French).unwrap(), "juin")
+        }
+    }
+
+    mod iso_week {
+        use super::*;
+
+        #[test]
+        fn iso_week_and_day_of_year_are_rendered() {
+            // 2015-01-09 is the 9th day of the year, and falls in ISO week 2.
+            let date = local::LocalDate::ymd(2015, local::Month::January, 9).unwrap();
+            assert_eq!(DateFormat::parse("{:W}-W{:w}-{:o}").unwrap().format(date).unwrap(), "2015-W2-9")
+        }
+
+        #[test]
+        fn new_years_day_can_fall_in_the_previous_iso_year() {
+            // 2016-01-01 is a Friday, so it falls in the last (53rd) ISO
+            // week of 2015, not week 1 of 2016.
+            let date = local::LocalDate::ymd(2016, local::Month::January, 1).unwrap();
+            assert_eq!(DateFormat::parse("{:W}-W{:w}").unwrap().format(date).unwrap(), "2015-W53");
+
+            // Same boundary, a different year: 2021-01-01 is also a Friday,
+            // landing in week 53 of 2020.
+            let date = local::LocalDate::ymd(2021, local::Month::January, 1).unwrap();
+            assert_eq!(DateFormat::parse("{:W}-W{:w}").unwrap().format(date).unwrap(), "2020-W53");
+        }
+    }
 }
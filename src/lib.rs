@@ -11,3 +11,4 @@ pub mod local;
 pub mod instant;
 pub mod duration;
 pub mod format;
+pub mod calendar;
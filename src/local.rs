@@ -0,0 +1,238 @@
+/// A month of the Gregorian calendar.
+#[derive(PartialEq, Eq, Clone, Show)]
+pub enum Month {
+    January,   February,  March,     April,
+    May,       June,      July,      August,
+    September, October,   November,  December,
+}
+
+impl Copy for Month { }
+
+/// A day of the week.
+#[derive(PartialEq, Eq, Clone, Show)]
+pub enum Weekday {
+    Monday, Tuesday, Wednesday, Thursday, Friday, Saturday, Sunday,
+}
+
+impl Copy for Weekday { }
+
+/// Accessors shared by anything that names a calendar date.
+pub trait DatePiece {
+    fn year(&self) -> i64;
+    fn year_of_century(&self) -> i64;
+    fn month(&self) -> Month;
+    fn day(&self) -> i8;
+    fn weekday(&self) -> Weekday;
+
+    /// The 1-based day count from January 1st, accounting for leap years.
+    fn day_of_year(&self) -> i64 {
+        let days_before_month = [0, 31, 59, 90, 120, 151, 181, 212, 243, 273, 304, 334];
+        let month_index = month_index(self.month());
+
+        let mut ordinal = days_before_month[month_index] + self.day() as i64;
+        if month_index >= 2 && is_leap_year(self.year()) {
+            ordinal += 1;
+        }
+        ordinal
+    }
+
+    /// The ISO 8601 week-date (year, week, weekday) for this date. The ISO
+    /// week year can differ from the calendar year at the very start or end
+    /// of December/January: `week == 0` means the date actually falls in
+    /// the last week of the previous ISO year, and `week == 53` falls in
+    /// week 1 of the next ISO year unless that year really does have 53
+    /// weeks.
+    fn iso_week_date(&self) -> (i64, u8, Weekday) {
+        let ordinal = self.day_of_year();
+        let weekday = monday_based_index(self.weekday());
+        // ISO's week arithmetic wants a 1-indexed weekday (Monday=1..Sunday=7);
+        // `weekday` here is 0-indexed, so +10 becomes +9 to compensate.
+        let week = (ordinal - weekday + 9) / 7;
+
+        let (year, week) = if week < 1 {
+            (self.year() - 1, iso_weeks_in_year(self.year() - 1))
+        }
+        else if week > iso_weeks_in_year(self.year()) {
+            (self.year() + 1, 1)
+        }
+        else {
+            (self.year(), week)
+        };
+
+        (year, week as u8, self.weekday())
+    }
+}
+
+#[derive(PartialEq, Eq, Clone, Show)]
+pub enum Error {
+    OutOfRange,
+}
+
+impl Copy for Error { }
+
+/// A calendar date with no time-of-day component.
+#[derive(PartialEq, Eq, Clone, Show)]
+pub struct LocalDate {
+    year:  i64,
+    month: Month,
+    day:   i8,
+}
+
+impl Copy for LocalDate { }
+
+impl LocalDate {
+    pub fn ymd(year: i64, month: Month, day: i8) -> Result<LocalDate, Error> {
+        if day < 1 || day > days_in_month(year, month) {
+            return Err(Error::OutOfRange);
+        }
+
+        Ok(LocalDate { year: year, month: month, day: day })
+    }
+
+    /// The day after this one, rolling over into the next month or year as needed.
+    pub fn succ(self) -> LocalDate {
+        if self.day < days_in_month(self.year, self.month) {
+            LocalDate { day: self.day + 1, .. self }
+        }
+        else {
+            match self.month {
+                Month::December => LocalDate { year: self.year + 1, month: Month::January, day: 1 },
+                other           => LocalDate { month: month_succ(other), day: 1, .. self },
+            }
+        }
+    }
+}
+
+impl DatePiece for LocalDate {
+    fn year(&self) -> i64 { self.year }
+    fn year_of_century(&self) -> i64 { self.year % 100 }
+    fn month(&self) -> Month { self.month }
+    fn day(&self) -> i8 { self.day }
+    fn weekday(&self) -> Weekday { weekday_of(self.year, self.month, self.day as i64) }
+}
+
+pub fn is_leap_year(year: i64) -> bool {
+    (year % 4 == 0 && year % 100 != 0) || year % 400 == 0
+}
+
+/// The number of days in a given month of a given year, accounting for leap years.
+pub fn days_in_month(year: i64, month: Month) -> i8 {
+    use self::Month::*;
+    match month {
+        January | March | May | July | August | October | December => 31,
+        April | June | September | November                        => 30,
+        February => if is_leap_year(year) { 29 } else { 28 },
+    }
+}
+
+fn month_succ(month: Month) -> Month {
+    use self::Month::*;
+    match month {
+        January   => February,  February => March,     March     => April,
+        April     => May,       May      => June,      June      => July,
+        July      => August,    August   => September, September => October,
+        October   => November,  November => December,  December  => January,
+    }
+}
+
+/// The 0-based index of a month within its year: January is 0, December is 11.
+fn month_index(month: Month) -> usize {
+    use self::Month::*;
+    match month {
+        January => 0,  February => 1,  March     => 2,  April    => 3,
+        May     => 4,  June     => 5,  July      => 6,  August   => 7,
+        September => 8, October => 9, November  => 10, December => 11,
+    }
+}
+
+/// Monday-based weekday index: Monday is 0, Sunday is 6.
+fn monday_based_index(day: Weekday) -> i64 {
+    use self::Weekday::*;
+    match day {
+        Monday => 0, Tuesday => 1, Wednesday => 2, Thursday => 3,
+        Friday  => 4, Saturday => 5, Sunday    => 6,
+    }
+}
+
+/// Whether `year` has 53 ISO weeks: true iff January 1st is a Thursday, or
+/// it's a leap year that starts on a Wednesday.
+fn iso_weeks_in_year(year: i64) -> i64 {
+    let jan1 = monday_based_index(LocalDate::ymd(year, Month::January, 1).unwrap().weekday());
+    if jan1 == 3 || (jan1 == 2 && is_leap_year(year)) { 53 } else { 52 }
+}
+
+/// Sakamoto's algorithm, used here rather than a day-number representation
+/// so `LocalDate` stays a plain year/month/day triple.
+fn weekday_of(year: i64, month: Month, day: i64) -> Weekday {
+    use self::Month::*;
+    use self::Weekday::*;
+
+    let month_number = match month {
+        January => 1,  February => 2,  March     => 3,  April    => 4,
+        May     => 5,  June     => 6,  July      => 7,  August   => 8,
+        September => 9, October => 10, November  => 11, December => 12,
+    };
+
+    let t = [0, 3, 2, 5, 0, 3, 5, 1, 4, 6, 2, 4];
+    let y = if month_number < 3 { year - 1 } else { year };
+    let sunday_based = (y + y / 4 - y / 100 + y / 400 + t[month_number - 1] + day) % 7;
+
+    match (sunday_based + 6) % 7 {
+        0 => Monday, 1 => Tuesday, 2 => Wednesday, 3 => Thursday,
+        4 => Friday, 5 => Saturday, _ => Sunday,
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn rejects_the_30th_of_february() {
+        assert_eq!(LocalDate::ymd(2015, Month::February, 30), Err(Error::OutOfRange))
+    }
+
+    #[test]
+    fn accepts_the_29th_of_february_in_a_leap_year() {
+        assert!(LocalDate::ymd(2016, Month::February, 29).is_ok())
+    }
+
+    #[test]
+    fn succ_rolls_over_into_the_next_month() {
+        let date = LocalDate::ymd(2015, Month::January, 31).unwrap();
+        assert_eq!(date.succ(), LocalDate::ymd(2015, Month::February, 1).unwrap())
+    }
+
+    #[test]
+    fn succ_rolls_over_into_the_next_year() {
+        let date = LocalDate::ymd(2015, Month::December, 31).unwrap();
+        assert_eq!(date.succ(), LocalDate::ymd(2016, Month::January, 1).unwrap())
+    }
+
+    #[test]
+    fn weekday_of_a_known_date() {
+        // 2015-01-01 was a Thursday.
+        assert_eq!(LocalDate::ymd(2015, Month::January, 1).unwrap().weekday(), Weekday::Thursday)
+    }
+
+    #[test]
+    fn thursday_new_year_has_53_weeks() {
+        let jan1 = LocalDate::ymd(2015, Month::January, 1).unwrap();
+        assert_eq!(jan1.weekday(), Weekday::Thursday);
+        assert_eq!(super::iso_weeks_in_year(2015), 53);
+    }
+
+    #[test]
+    fn leap_year_starting_on_wednesday_has_53_weeks() {
+        let jan1 = LocalDate::ymd(2020, Month::January, 1).unwrap();
+        assert_eq!(jan1.weekday(), Weekday::Wednesday);
+        assert_eq!(super::iso_weeks_in_year(2020), 53);
+    }
+
+    #[test]
+    fn ordinary_year_has_52_weeks() {
+        let jan1 = LocalDate::ymd(2016, Month::January, 1).unwrap();
+        assert_eq!(jan1.weekday(), Weekday::Friday);
+        assert_eq!(super::iso_weeks_in_year(2016), 52);
+    }
+}